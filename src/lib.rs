@@ -8,93 +8,545 @@ pub enum PlaneCut<T> {
     },
 }
 
-pub trait Plane: Sized + Clone {
+/// A monoid describing an aggregate over a subtree's values, so callers
+/// can query a whole subtree without walking it.
+///
+/// `combine` must be associative, and the type should have an identity
+/// element with respect to it (though `Summarize` has no use for one,
+/// since every live node holds at least one value and an empty tree
+/// reports no summary at all).
+pub trait Summarize {
+    type Summary: Clone;
+    fn summary(&self) -> Self::Summary;
+    fn combine(a: &Self::Summary, b: &Self::Summary) -> Self::Summary;
+}
+
+pub trait Plane: Sized + Clone + Summarize {
     fn cut(&self, Self) -> PlaneCut<Self>;
     fn is_aligned(&self, &Self) -> bool;
 }
 
-fn add_side<I>(side: &mut Option<Box<BspNode<I::Item>>>, mut iter: I)
-where I: Iterator, I::Item: Plane {
-    match *side {
-        None => {
-            if let Some(p) = iter.next() {
-                let mut node = BspNode::new(p);
-                for p in iter {
-                    node.insert(p)
-                }
-                *side = Some(Box::new(node));
-            }
-        }
-        Some(ref mut node) => {
-            for p in iter {
-                node.insert(p)
-            }
+/// Tuning knobs for `BspNode::build_with_config`.
+pub struct BuildConfig {
+    /// How many candidate splitting planes to score at each node.
+    /// Scoring every remaining plane would make construction quadratic;
+    /// sampling a fixed number keeps it near O(n log n).
+    pub max_candidates: usize,
+    /// Weight given to the number of straddling planes relative to the
+    /// front/back imbalance when scoring a candidate splitter.
+    pub split_weight: usize,
+}
+
+impl Default for BuildConfig {
+    fn default() -> Self {
+        BuildConfig {
+            max_candidates: 5,
+            split_weight: 8,
         }
     }
 }
 
+// Raw slot index into a `BspNode` arena. Unlike `NodeHandle`, this
+// carries no generation, so it's only safe to use for links the tree
+// itself maintains (front/back/free_head) and that are always kept
+// pointing at a live node or `None`. Anything handed out to callers
+// across further mutations must go through `NodeHandle` instead.
+type RawIndex = u32;
+
+/// A handle to a node inside a `BspNode` arena, returned by `root()` and
+/// accepted by `remove()`. Tags the slot's generation at the time the
+/// handle was taken, so a handle from before a `remove` is rejected
+/// rather than silently aliasing whatever unrelated node `alloc` later
+/// recycles that slot for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NodeHandle {
+    index: RawIndex,
+    generation: u32,
+}
 
-pub struct BspNode<T> {
+struct Node<T: Summarize> {
     values: Vec<T>,
-    front: Option<Box<BspNode<T>>>,
-    back: Option<Box<BspNode<T>>>,
+    // While the node is live, `front`/`back` point at its children.
+    // Once freed, `front` instead holds the next entry on the free list
+    // (or `None` if it is the list's tail) and `back` is unused.
+    front: Option<RawIndex>,
+    back: Option<RawIndex>,
+    // The combined summary of `values` and both children, kept up to
+    // date by `recompute_summary` every time this node or a descendant
+    // changes.
+    summary: T::Summary,
+    // Bumped every time this slot is freed, so a `NodeHandle` taken
+    // before the free no longer matches once the slot is recycled.
+    generation: u32,
 }
 
-impl<T: Plane> BspNode<T> {
-    pub fn new(value: T) -> Self {
-        Self {
+impl<T: Summarize> Node<T> {
+    fn new(value: T, generation: u32) -> Self {
+        Node {
+            summary: value.summary(),
             values: vec![value],
             front: None,
             back: None,
+            generation,
+        }
+    }
+}
+
+/// A binary space partitioning tree, stored as a flat arena of nodes
+/// addressed by `NodeHandle` rather than as individually boxed children.
+pub struct BspNode<T: Summarize> {
+    nodes: Vec<Node<T>>,
+    free_head: Option<RawIndex>,
+    root: Option<RawIndex>,
+}
+
+impl<T: Plane> BspNode<T> {
+    pub fn new(value: T) -> Self {
+        let mut tree = BspNode {
+            nodes: Vec::new(),
+            free_head: None,
+            root: None,
+        };
+        let root = tree.alloc(value);
+        tree.root = Some(root);
+        tree
+    }
+
+    /// Builds a tree from `planes` using `BuildConfig::default()`. See
+    /// `build_with_config` for how the splitting planes are chosen.
+    pub fn build(planes: Vec<T>) -> Self {
+        Self::build_with_config(planes, BuildConfig::default())
+    }
+
+    /// Builds a tree from `planes`, choosing each node's splitting plane
+    /// with the classic BSP heuristic instead of taking planes in
+    /// whatever order they arrive (as repeated `insert` does). At each
+    /// node, `config.max_candidates` planes are sampled and each is
+    /// scored by how many of the remaining planes it would straddle and
+    /// how unevenly it would split the rest between front and back; the
+    /// lowest-scoring candidate becomes the splitter. This keeps
+    /// construction close to O(n log n) while producing far shallower,
+    /// better-balanced trees than insertion order allows.
+    pub fn build_with_config(planes: Vec<T>, config: BuildConfig) -> Self {
+        let mut tree = BspNode {
+            nodes: Vec::new(),
+            free_head: None,
+            root: None,
+        };
+        tree.root = tree.build_node(planes, &config);
+        tree
+    }
+
+    fn build_node(&mut self, mut planes: Vec<T>, config: &BuildConfig) -> Option<RawIndex> {
+        if planes.is_empty() {
+            return None;
+        }
+        if planes.len() == 1 {
+            return Some(self.alloc(planes.pop().unwrap()));
+        }
+
+        let candidate_count = cmp::min(config.max_candidates, planes.len());
+        // Stride evenly across `planes` rather than taking its literal
+        // prefix: callers (and our own recursive splits, which `extend`
+        // front/back in whatever order `cut` produced them) routinely
+        // hand in runs of sorted or clustered planes, and scoring only
+        // `planes[0..candidate_count]` in that case samples one cluster
+        // instead of the whole remaining set.
+        let best_index = (0 .. candidate_count)
+            .map(|k| k * planes.len() / candidate_count)
+            .min_by_key(|&i| Self::score_candidate(&planes, i, config.split_weight))
+            .unwrap();
+
+        let splitter = planes.swap_remove(best_index);
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        let mut siblings = Vec::new();
+        for plane in planes {
+            match splitter.cut(plane) {
+                PlaneCut::Sibling(value) => siblings.push(value),
+                PlaneCut::Cut { front: f, back: b } => {
+                    front.extend(f);
+                    back.extend(b);
+                }
+            }
+        }
+
+        let handle = self.alloc(splitter);
+        self.nodes[handle as usize].values.extend(siblings);
+
+        let front_handle = self.build_node(front, config);
+        let back_handle = self.build_node(back, config);
+        self.nodes[handle as usize].front = front_handle;
+        self.nodes[handle as usize].back = back_handle;
+        self.recompute_summary(handle);
+
+        Some(handle)
+    }
+
+    // Scores `planes[index]` as a candidate splitter: a weighted count
+    // of how many other planes it would straddle, plus the imbalance
+    // between how many it would send front versus back. Lower is
+    // better.
+    fn score_candidate(planes: &[T], index: usize, split_weight: usize) -> usize {
+        let candidate = &planes[index];
+        let mut front_count: usize = 0;
+        let mut back_count: usize = 0;
+        let mut straddle_count: usize = 0;
+
+        for (i, plane) in planes.iter().enumerate() {
+            if i == index {
+                continue;
+            }
+            if let PlaneCut::Cut { front, back } = candidate.cut(plane.clone()) {
+                match (front.is_empty(), back.is_empty()) {
+                    (false, false) => straddle_count += 1,
+                    (false, true) => front_count += 1,
+                    (true, false) => back_count += 1,
+                    (true, true) => {}
+                }
+            }
         }
+
+        split_weight * straddle_count + front_count.abs_diff(back_count)
+    }
+
+    fn alloc(&mut self, value: T) -> RawIndex {
+        match self.free_head {
+            Some(index) => {
+                self.free_head = self.nodes[index as usize].front;
+                let generation = self.nodes[index as usize].generation;
+                self.nodes[index as usize] = Node::new(value, generation);
+                index
+            }
+            None => {
+                let index = self.nodes.len() as RawIndex;
+                self.nodes.push(Node::new(value, 0));
+                index
+            }
+        }
+    }
+
+    fn free(&mut self, index: RawIndex) {
+        self.nodes[index as usize].values.clear();
+        self.nodes[index as usize].generation = self.nodes[index as usize].generation.wrapping_add(1);
+        self.nodes[index as usize].front = self.free_head;
+        self.nodes[index as usize].back = None;
+        self.free_head = Some(index);
+    }
+
+    /// The handle of the tree's root node, if any values have been
+    /// inserted.
+    pub fn root(&self) -> Option<NodeHandle> {
+        self.root.map(|index| NodeHandle {
+            index,
+            generation: self.nodes[index as usize].generation,
+        })
     }
 
     pub fn is_leaf(&self) -> bool {
-        self.front.is_none() && self.back.is_none()
+        match self.root {
+            Some(handle) => {
+                let node = &self.nodes[handle as usize];
+                node.front.is_none() && node.back.is_none()
+            }
+            None => true,
+        }
+    }
+
+    fn add_side<I>(&mut self, side: Option<RawIndex>, mut iter: I) -> Option<RawIndex>
+    where I: Iterator<Item = T> {
+        match side {
+            None => {
+                match iter.next() {
+                    Some(p) => {
+                        let handle = self.alloc(p);
+                        for p in iter {
+                            self.insert_at(handle, p);
+                        }
+                        Some(handle)
+                    }
+                    None => None,
+                }
+            }
+            Some(handle) => {
+                for p in iter {
+                    self.insert_at(handle, p);
+                }
+                Some(handle)
+            }
+        }
     }
 
     pub fn insert(&mut self, value: T) {
-        match self.values[0].cut(value) {
-            PlaneCut::Sibling(value) => self.values.push(value),
+        match self.root {
+            Some(handle) => self.insert_at(handle, value),
+            None => self.root = Some(self.alloc(value)),
+        }
+    }
+
+    fn insert_at(&mut self, handle: RawIndex, value: T) {
+        match self.nodes[handle as usize].values[0].cut(value) {
+            PlaneCut::Sibling(value) => self.nodes[handle as usize].values.push(value),
             PlaneCut::Cut { mut front, mut back } => {
-                add_side(&mut self.front, front.drain(..));
-                add_side(&mut self.back, back.drain(..));
+                let front_side = self.nodes[handle as usize].front;
+                let front_side = self.add_side(front_side, front.drain(..));
+                self.nodes[handle as usize].front = front_side;
+
+                let back_side = self.nodes[handle as usize].back;
+                let back_side = self.add_side(back_side, back.drain(..));
+                self.nodes[handle as usize].back = back_side;
+            }
+        }
+        self.recompute_summary(handle);
+    }
+
+    // Recomputes `handle`'s cached summary from its own values and its
+    // children's cached summaries. Assumes both children are already
+    // up to date, so callers must recompute bottom-up.
+    fn recompute_summary(&mut self, handle: RawIndex) {
+        let idx = handle as usize;
+        let mut summary = self.nodes[idx].values[0].summary();
+        for value in &self.nodes[idx].values[1..] {
+            summary = T::combine(&summary, &value.summary());
+        }
+        if let Some(h) = self.nodes[idx].front {
+            let front_summary = self.nodes[h as usize].summary.clone();
+            summary = T::combine(&front_summary, &summary);
+        }
+        if let Some(h) = self.nodes[idx].back {
+            let back_summary = self.nodes[h as usize].summary.clone();
+            summary = T::combine(&summary, &back_summary);
+        }
+        self.nodes[idx].summary = summary;
+    }
+
+    /// Removes the node at `handle`, along with all the values it holds,
+    /// splicing its children back into the tree in its place. Returns
+    /// `false` if `handle` is not part of this tree, including when its
+    /// slot has since been recycled for an unrelated node.
+    pub fn remove(&mut self, handle: NodeHandle) -> bool {
+        if !self.is_current(handle) {
+            return false;
+        }
+        let index = handle.index;
+        match self.root {
+            Some(root) if root == index => {
+                self.root = self.splice(index);
+                true
+            }
+            Some(root) => self.remove_at(root, index),
+            None => false,
+        }
+    }
+
+    // Whether `handle` still refers to the node it was taken from,
+    // i.e. its slot hasn't been freed and recycled since.
+    fn is_current(&self, handle: NodeHandle) -> bool {
+        (handle.index as usize) < self.nodes.len()
+            && self.nodes[handle.index as usize].generation == handle.generation
+    }
+
+    fn remove_at(&mut self, parent: RawIndex, handle: RawIndex) -> bool {
+        if self.nodes[parent as usize].front == Some(handle) {
+            self.nodes[parent as usize].front = self.splice(handle);
+            self.recompute_summary(parent);
+            return true;
+        }
+        if self.nodes[parent as usize].back == Some(handle) {
+            self.nodes[parent as usize].back = self.splice(handle);
+            self.recompute_summary(parent);
+            return true;
+        }
+        let (front, back) = (self.nodes[parent as usize].front, self.nodes[parent as usize].back);
+        if let Some(h) = front {
+            if self.remove_at(h, handle) {
+                self.recompute_summary(parent);
+                return true;
+            }
+        }
+        match back {
+            Some(h) => {
+                let found = self.remove_at(h, handle);
+                if found {
+                    self.recompute_summary(parent);
+                }
+                found
+            }
+            None => false,
+        }
+    }
+
+    // Detaches `handle`, freeing it and returning whatever should occupy
+    // its slot in the parent afterwards. If it had two children, the
+    // `back` subtree's values are re-inserted under the `front` one,
+    // since only a single handle can be spliced back in.
+    fn splice(&mut self, handle: RawIndex) -> Option<RawIndex> {
+        let front = self.nodes[handle as usize].front;
+        let back = self.nodes[handle as usize].back;
+        self.free(handle);
+        match (front, back) {
+            (Some(f), None) => Some(f),
+            (None, Some(b)) => Some(b),
+            (None, None) => None,
+            (Some(f), Some(b)) => {
+                for value in self.drain_values(b) {
+                    self.insert_at(f, value);
+                }
+                Some(f)
             }
         }
     }
 
+    // Collects every value under `handle`, front-to-back order does not
+    // matter here since they are about to be re-inserted, and frees the
+    // nodes along the way.
+    fn drain_values(&mut self, handle: RawIndex) -> Vec<T> {
+        let mut values = self.nodes[handle as usize].values.clone();
+        let front = self.nodes[handle as usize].front;
+        let back = self.nodes[handle as usize].back;
+        self.free(handle);
+        if let Some(h) = front {
+            values.extend(self.drain_values(h));
+        }
+        if let Some(h) = back {
+            values.extend(self.drain_values(h));
+        }
+        values
+    }
+
     pub fn get_depth(&self) -> usize {
-        let df = match self.front {
-            Some(ref node) => node.get_depth(),
+        match self.root {
+            Some(handle) => self.get_depth_at(handle),
+            None => 0,
+        }
+    }
+
+    fn get_depth_at(&self, handle: RawIndex) -> usize {
+        let node = &self.nodes[handle as usize];
+        let df = match node.front {
+            Some(h) => self.get_depth_at(h),
             None => 0,
         };
-        let db = match self.back {
-            Some(ref node) => node.get_depth(),
+        let db = match node.back {
+            Some(h) => self.get_depth_at(h),
             None => 0,
         };
         1 + cmp::max(df, db)
     }
 
     pub fn order(&self, base: &T, out: &mut Vec<T>) {
-        let (former, latter) = if base.is_aligned(&self.values[0]) {
-            (&self.front, &self.back)
+        out.extend(self.iter_order(base).cloned());
+    }
+
+    pub fn order_self(&self, out: &mut Vec<T>) {
+        if let Some(handle) = self.root {
+            let base = self.nodes[handle as usize].values[0].clone();
+            self.order(&base, out);
+        }
+    }
+
+    /// Streams the tree's values front-to-back with respect to `base`,
+    /// without collecting them into a `Vec` first. Unlike `order`, this
+    /// can be combined with `take_while`/`enumerate`/early `break` to
+    /// stop once a caller has seen enough.
+    pub fn iter_order<'a>(&'a self, base: &T) -> Order<'a, T> {
+        let mut stack = Vec::new();
+        if let Some(root) = self.root {
+            stack.push(Frame::Visit(root));
+        }
+        Order {
+            tree: self,
+            base: base.clone(),
+            stack,
+        }
+    }
+
+    /// The cached summary of the whole tree, or `None` if the tree holds
+    /// no values (including after `remove` has emptied it out).
+    pub fn node_summary(&self) -> Option<T::Summary> {
+        self.root.map(|handle| self.nodes[handle as usize].summary.clone())
+    }
+
+    /// Yields the cached summary of each node, in the same front-to-back
+    /// visibility order as `order`, so a caller can e.g. cull whole
+    /// subtrees without visiting their individual values.
+    pub fn fold_front_to_back<'a>(&'a self, base: &T) -> impl Iterator<Item = &'a T::Summary> {
+        let mut handles = Vec::new();
+        if let Some(root) = self.root {
+            self.order_handles(root, base, &mut handles);
+        }
+        handles.into_iter().map(move |h| &self.nodes[h as usize].summary)
+    }
+
+    fn order_handles(&self, handle: RawIndex, base: &T, out: &mut Vec<RawIndex>) {
+        let node = &self.nodes[handle as usize];
+        let (former, latter) = if base.is_aligned(&node.values[0]) {
+            (node.front, node.back)
         } else {
-            (&self.back, &self.front)
+            (node.back, node.front)
         };
 
-        if let Some(ref node) = *former {
-            node.order(base, out);
+        if let Some(h) = former {
+            self.order_handles(h, base, out);
         }
 
-        out.extend_from_slice(&self.values);
+        out.push(handle);
 
-        if let Some(ref node) = *latter {
-            node.order(base, out);
+        if let Some(h) = latter {
+            self.order_handles(h, base, out);
         }
     }
+}
 
-    pub fn order_self(&self, out: &mut Vec<T>) {
-        self.order(&self.values[0], out);
+// A pending unit of work for `Order`'s explicit-stack traversal: either
+// descend into a node (deciding its former/latter children), or emit
+// its values one at a time.
+enum Frame {
+    Visit(RawIndex),
+    Values(RawIndex, usize),
+}
+
+/// Lazy front-to-back iterator over a `BspNode`'s values, returned by
+/// `iter_order`. Walks the tree with an explicit stack rather than
+/// recursion, so it can be stopped early without ever visiting the
+/// rest of the tree.
+pub struct Order<'a, T: Plane + 'a> {
+    tree: &'a BspNode<T>,
+    base: T,
+    stack: Vec<Frame>,
+}
+
+impl<'a, T: Plane> Iterator for Order<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        while let Some(frame) = self.stack.pop() {
+            match frame {
+                Frame::Visit(handle) => {
+                    let node = &self.tree.nodes[handle as usize];
+                    let (former, latter) = if self.base.is_aligned(&node.values[0]) {
+                        (node.front, node.back)
+                    } else {
+                        (node.back, node.front)
+                    };
+                    if let Some(h) = latter {
+                        self.stack.push(Frame::Visit(h));
+                    }
+                    self.stack.push(Frame::Values(handle, 0));
+                    if let Some(h) = former {
+                        self.stack.push(Frame::Visit(h));
+                    }
+                }
+                Frame::Values(handle, index) => {
+                    let values = &self.tree.nodes[handle as usize].values;
+                    if index < values.len() {
+                        self.stack.push(Frame::Values(handle, index + 1));
+                        return Some(&values[index]);
+                    }
+                }
+            }
+        }
+        None
     }
 }
 
@@ -130,24 +582,31 @@ mod tests {
         }
     }
 
+    impl Summarize for Plane1D {
+        type Summary = usize;
+
+        fn summary(&self) -> usize {
+            1
+        }
+
+        fn combine(a: &usize, b: &usize) -> usize {
+            a + b
+        }
+    }
+
 
     #[test]
-    fn test_add_side() {
-        let mut node_opt = None;
-        let p0: Vec<Plane1D> = Vec::new();
-        add_side(&mut node_opt, p0.into_iter());
-        assert!(node_opt.is_none());
+    fn test_insert_splits() {
+        let mut node = BspNode::new(Plane1D(1, true));
+        assert!(node.is_leaf());
 
-        let p1 = Plane1D(1, true);
-        add_side(&mut node_opt, Some(p1.clone()).into_iter());
-        assert_eq!(node_opt.as_ref().unwrap().values, vec![p1.clone()]);
-        assert!(node_opt.as_ref().unwrap().is_leaf());
+        node.insert(Plane1D(0, false));
+        node.insert(Plane1D(2, false));
+        assert!(!node.is_leaf());
 
-        let p23 = vec![Plane1D(0, false), Plane1D(2, false)];
-        add_side(&mut node_opt, p23.into_iter());
-        let node = node_opt.unwrap();
-        assert_eq!(node.values, vec![p1.clone()]);
-        assert!(node.front.is_some() && node.back.is_some());
+        let mut out = Vec::new();
+        node.order_self(&mut out);
+        assert_eq!(out, vec![Plane1D(0, false), Plane1D(1, true), Plane1D(2, false)]);
     }
 
     #[test]
@@ -179,4 +638,158 @@ mod tests {
         out2.sort_by_key(|p| p.0);
         assert_eq!(out, out2);
     }
+
+    #[test]
+    fn test_remove_reuses_slot() {
+        let mut node = BspNode::new(Plane1D(0, true));
+        node.insert(Plane1D(6, true));
+        node.insert(Plane1D(-5, true));
+
+        let root = node.root().unwrap();
+        let nodes_before_remove = node.nodes.len();
+        assert!(node.remove(root));
+
+        // Removing an interior node re-inserts its surviving child's
+        // values (one more alloc), and the insert below needs another:
+        // without free-list reuse the arena would have grown by two
+        // slots by now, so a `nodes.len()` that is still unchanged is
+        // proof the freed slots were handed back out instead.
+        node.insert(Plane1D(1, true));
+        assert_eq!(node.nodes.len(), nodes_before_remove);
+
+        let mut out = Vec::new();
+        node.order_self(&mut out);
+        let mut out2 = out.clone();
+        out2.sort_by_key(|p| p.0);
+        assert_eq!(out, out2);
+    }
+
+    #[test]
+    fn test_node_summary_counts_values() {
+        let mut node = BspNode::new(Plane1D(0, true));
+        assert_eq!(node.node_summary(), Some(1));
+        node.insert(Plane1D(6, true));
+        node.insert(Plane1D(-5, true));
+        node.insert(Plane1D(0, true));
+        assert_eq!(node.node_summary(), Some(4));
+    }
+
+    #[test]
+    fn test_node_summary_none_when_empty() {
+        let mut node = BspNode::new(Plane1D(0, true));
+        let root = node.root().unwrap();
+        assert!(node.remove(root));
+        assert_eq!(node.node_summary(), None);
+    }
+
+    #[test]
+    fn test_build_is_shallower_than_sorted_insert() {
+        let planes: Vec<Plane1D> = (0 .. 64).map(|i| Plane1D(i, true)).collect();
+
+        let mut inserted = BspNode::new(planes[0].clone());
+        for plane in &planes[1..] {
+            inserted.insert(plane.clone());
+        }
+        // Inserting already-sorted planes in order degenerates into a
+        // linear chain, one plane deep per insert.
+        assert_eq!(inserted.get_depth(), planes.len());
+
+        let built = BspNode::build(planes.clone());
+        // Sorted input is exactly the case the heuristic exists for: a
+        // literal-prefix candidate sample would see the same clustered
+        // run of planes at every node and peel off only a few planes
+        // per level (depth 20 here, against 7 for the strided sample).
+        // Bound well above the ~log2(n) a representative sample should
+        // achieve, so only a regression to something close to
+        // `insert`'s linear chain fails this.
+        let log2_floor = usize::BITS as usize - 1 - planes.len().leading_zeros() as usize;
+        let bound = 4 * (log2_floor + 1);
+        assert!(built.get_depth() <= bound, "depth {} exceeded bound {}", built.get_depth(), bound);
+
+        let mut out = Vec::new();
+        built.order_self(&mut out);
+        let mut expected = planes;
+        expected.sort_by_key(|p| p.0);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_build_empty() {
+        let tree: BspNode<Plane1D> = BspNode::build(Vec::new());
+        assert!(tree.is_leaf());
+        assert_eq!(tree.get_depth(), 0);
+        let mut out = Vec::new();
+        tree.order_self(&mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_iter_order_matches_order() {
+        let mut rng = rand::thread_rng();
+        let mut node = BspNode::new(Plane1D(0, true));
+        for _ in 0 .. 100 {
+            let plane = Plane1D(rng.gen(), true);
+            node.insert(plane);
+        }
+
+        let mut out = Vec::new();
+        node.order_self(&mut out);
+        let base = Plane1D(0, true);
+        let streamed: Vec<Plane1D> = node.iter_order(&base).cloned().collect();
+        assert_eq!(out, streamed);
+    }
+
+    #[test]
+    fn test_iter_order_stops_early() {
+        let mut node = BspNode::new(Plane1D(0, true));
+        node.insert(Plane1D(6, true));
+        node.insert(Plane1D(-5, true));
+        node.insert(Plane1D(10, true));
+
+        let base = Plane1D(0, true);
+        let first_two: Vec<Plane1D> = node.iter_order(&base).take(2).cloned().collect();
+        assert_eq!(first_two, vec![Plane1D(-5, true), Plane1D(0, true)]);
+    }
+
+    #[test]
+    fn test_fold_front_to_back_visits_every_node() {
+        let mut node = BspNode::new(Plane1D(0, true));
+        node.insert(Plane1D(6, true));
+        node.insert(Plane1D(-5, true));
+
+        // One node per distinct plane here, so the fold has as many
+        // entries as `order` has values, and the root's cached subtree
+        // summary (the largest, since it covers everything) matches
+        // `node_summary`.
+        let mut out = Vec::new();
+        node.order_self(&mut out);
+        let summaries: Vec<usize> = node.fold_front_to_back(&Plane1D(0, true)).cloned().collect();
+        assert_eq!(summaries.len(), out.len());
+        assert_eq!(summaries.into_iter().max(), node.node_summary());
+    }
+
+    #[test]
+    fn test_remove_rejects_stale_handle() {
+        let mut node = BspNode::new(Plane1D(0, true));
+        node.insert(Plane1D(6, true));
+        node.insert(Plane1D(-5, true));
+
+        let root = node.root().unwrap();
+        assert!(node.remove(root));
+
+        // The first insert below recycles the freed root slot for a
+        // brand-new, unrelated value (see `test_remove_reuses_slot`);
+        // the second one grows the arena. The old
+        // `root` handle must not be allowed to reach in and delete
+        // whatever now lives there.
+        node.insert(Plane1D(1, true));
+        node.insert(Plane1D(2, true));
+        assert!(!node.remove(root));
+
+        let mut out = Vec::new();
+        node.order_self(&mut out);
+        let mut expected = vec![Plane1D(-5, true), Plane1D(1, true), Plane1D(2, true), Plane1D(6, true)];
+        expected.sort_by_key(|p| p.0);
+        assert_eq!(out, expected);
+    }
 }